@@ -0,0 +1,125 @@
+use percent_encoding::percent_decode_str;
+use tauri::{AppHandle, Url};
+
+/// Compose-window parameters extracted from a `mailto:`/`nubo://` URI.
+pub struct MailtoParams {
+    pub recipient: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Parses a `mailto:` or `nubo://compose` URI into [`MailtoParams`],
+/// percent-decoding each component. `cc`/`bcc` addresses are folded into the
+/// recipient list alongside the primary addresses, matching how a mail
+/// client's "To" field treats a comma-separated address list.
+pub fn parse_mailto(uri: &str) -> Option<MailtoParams> {
+    let url = Url::parse(uri).ok()?;
+
+    match url.scheme() {
+        "mailto" => {
+            let decoded_path = percent_decode_str(url.path()).decode_utf8_lossy();
+            let mut recipients: Vec<String> = decoded_path
+                .split(',')
+                .map(|addr| addr.trim().to_string())
+                .filter(|addr| !addr.is_empty())
+                .collect();
+
+            let mut subject = String::new();
+            let mut body = String::new();
+            for (key, value) in url.query_pairs() {
+                match key.as_ref() {
+                    "cc" | "bcc" => recipients.push(value.into_owned()),
+                    "subject" => subject = value.into_owned(),
+                    "body" => body = value.into_owned(),
+                    _ => {}
+                }
+            }
+
+            Some(MailtoParams {
+                recipient: recipients.join(","),
+                subject,
+                body,
+            })
+        }
+        "nubo" if url.host_str() == Some("compose") => {
+            let mut recipient = String::new();
+            let mut subject = String::new();
+            let mut body = String::new();
+            for (key, value) in url.query_pairs() {
+                match key.as_ref() {
+                    "to" => recipient = value.into_owned(),
+                    "subject" => subject = value.into_owned(),
+                    "body" => body = value.into_owned(),
+                    _ => {}
+                }
+            }
+
+            Some(MailtoParams {
+                recipient,
+                subject,
+                body,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Opens a compose window pre-filled from a `mailto:`/`nubo://` URI, if it
+/// parses as one.
+pub fn open_compose_from_uri(app: &AppHandle, uri: &str) {
+    if let Some(params) = parse_mailto(uri) {
+        let _ = crate::window_manager::open_compose(
+            app.clone(),
+            params.recipient,
+            params.subject,
+            params.body,
+        );
+    }
+}
+
+/// Scans process args — used both on cold start and by the single-instance
+/// callback — for the first `mailto:`/`nubo://` URI.
+pub fn find_uri_in_args<I: IntoIterator<Item = String>>(args: I) -> Option<String> {
+    args.into_iter()
+        .find(|arg| arg.starts_with("mailto:") || arg.starts_with("nubo://"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mailto_decodes_percent_encoded_recipient() {
+        let params = parse_mailto("mailto:%22John%20Doe%22%20%3Cjohn@x.com%3E").unwrap();
+        assert_eq!(params.recipient, "\"John Doe\" <john@x.com>");
+        assert_eq!(params.subject, "");
+        assert_eq!(params.body, "");
+    }
+
+    #[test]
+    fn mailto_splits_multiple_comma_separated_addresses() {
+        let params = parse_mailto("mailto:a@x.com,b@x.com, c@x.com").unwrap();
+        assert_eq!(params.recipient, "a@x.com,b@x.com,c@x.com");
+    }
+
+    #[test]
+    fn mailto_folds_cc_and_bcc_into_recipients() {
+        let params = parse_mailto("mailto:a@x.com?cc=b@x.com&bcc=c@x.com&subject=Hi&body=Hey").unwrap();
+        assert_eq!(params.recipient, "a@x.com,b@x.com,c@x.com");
+        assert_eq!(params.subject, "Hi");
+        assert_eq!(params.body, "Hey");
+    }
+
+    #[test]
+    fn nubo_compose_reads_to_subject_and_body() {
+        let params = parse_mailto("nubo://compose?to=a@x.com&subject=Hi&body=Hey").unwrap();
+        assert_eq!(params.recipient, "a@x.com");
+        assert_eq!(params.subject, "Hi");
+        assert_eq!(params.body, "Hey");
+    }
+
+    #[test]
+    fn rejects_unsupported_schemes() {
+        assert!(parse_mailto("https://nubo.email").is_none());
+    }
+}
@@ -0,0 +1,134 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::webview::PageLoadEvent;
+use tauri::{AppHandle, Manager, WebviewWindow};
+
+const SETTINGS_FILE: &str = "compatibility-rendering.json";
+const LOAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Persisted user override for the per-platform rendering workarounds.
+/// `None` means "auto" (apply the known-good flags, the default).
+#[derive(Serialize, Deserialize, Default)]
+struct CompatibilitySettings {
+    forced: Option<bool>,
+}
+
+fn settings_path(app: &AppHandle) -> tauri::Result<PathBuf> {
+    let dir = app.path().app_config_dir()?;
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join(SETTINGS_FILE))
+}
+
+fn load_settings(app: &AppHandle) -> CompatibilitySettings {
+    settings_path(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_settings(app: &AppHandle, settings: &CompatibilitySettings) {
+    if let Ok(path) = settings_path(app) {
+        if let Ok(json) = serde_json::to_string_pretty(settings) {
+            let _ = fs::write(path, json);
+        }
+    }
+}
+
+/// Applies known-good environment/webview flags for the current platform
+/// before any window is built, unless the user has permanently disabled
+/// compatibility rendering. This works around the blank-window failures seen
+/// on WebKitGTK with DMABUF-capable but misbehaving GPU drivers.
+pub fn apply_platform_workarounds(app: &AppHandle) {
+    let enabled = load_settings(app).forced.unwrap_or(true);
+    if !enabled {
+        return;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        for (key, value) in [
+            ("WEBKIT_DISABLE_DMABUF_RENDERER", "1"),
+            ("WEBKIT_DISABLE_COMPOSITING_MODE", "1"),
+        ] {
+            if std::env::var_os(key).is_none() {
+                std::env::set_var(key, value);
+            }
+        }
+    }
+}
+
+/// Persists whether compatibility rendering should be forced on, so users
+/// on problematic drivers can keep the workaround across restarts.
+#[tauri::command]
+pub fn set_compatibility_rendering(app: AppHandle, enabled: bool) {
+    save_settings(
+        &app,
+        &CompatibilitySettings {
+            forced: Some(enabled),
+        },
+    );
+}
+
+/// Tracks whether the main window finished its initial page load, so we can
+/// detect the "installed it and got a blank screen" failure mode.
+#[derive(Clone)]
+pub struct LoadWatcher {
+    loaded: Arc<AtomicBool>,
+}
+
+impl LoadWatcher {
+    pub fn new() -> Self {
+        Self {
+            loaded: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Callback for `WebviewWindowBuilder::on_page_load`.
+    pub fn on_page_load(&self) -> impl Fn(WebviewWindow, &tauri::webview::PageLoadPayload) + Send + Sync + 'static {
+        let loaded = self.loaded.clone();
+        move |_window, payload| {
+            if payload.event() == PageLoadEvent::Finished {
+                loaded.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Spawns a background check that opens the embedded diagnostics page if
+    /// the window never reports a finished load within [`LOAD_TIMEOUT`].
+    pub fn spawn_timeout_check(&self, app: &AppHandle) {
+        let loaded = self.loaded.clone();
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(LOAD_TIMEOUT).await;
+            if !loaded.load(Ordering::Relaxed) {
+                show_load_failure(&app, "the webview did not finish loading within the startup timeout");
+            }
+        });
+    }
+}
+
+/// Replaces the main window's contents with a diagnostics page reporting the
+/// OS, webview runtime version, and the load error, instead of leaving a
+/// white window with no explanation.
+pub fn show_load_failure(app: &AppHandle, error: &str) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+
+    let os = std::env::consts::OS;
+    let webview_version = tauri::webview_version().unwrap_or_else(|_| "unknown".to_string());
+    let body = format!(
+        "<body style=\"font-family:sans-serif;padding:2rem;line-height:1.5\">\
+         <h1>Nubo couldn't load</h1><p>{error}</p>\
+         <ul><li>OS: {os}</li><li>Webview: {webview_version}</li></ul>\
+         <button onclick=\"location.reload()\">Retry</button></body>"
+    );
+    let script = format!("document.documentElement.innerHTML = {:?};", body);
+    let _ = window.eval(&script);
+}
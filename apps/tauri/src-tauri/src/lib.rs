@@ -1,11 +1,59 @@
-use tauri::{WebviewUrl, WebviewWindowBuilder};
+mod deep_link;
+mod diagnostics;
+mod offline;
+mod tray;
+mod updater;
+mod window_manager;
+
+use tauri::{Manager, WebviewWindowBuilder, WindowEvent};
+use tauri_plugin_deep_link::DeepLinkExt;
+use tray::MailPollState;
+use updater::UpdaterPreferences;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tauri::Builder::default()
+    offline::register_protocol(tauri::Builder::default())
+        // Must be registered before any other plugin: forwards a second
+        // launch's argv (e.g. from clicking another `mailto:` link) to the
+        // already-running instance instead of spawning a new process.
+        .plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+            if let Some(uri) = deep_link::find_uri_in_args(argv) {
+                deep_link::open_compose_from_uri(app, &uri);
+            } else if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }))
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_process::init())
+        .plugin(tauri_plugin_updater::Builder::new().build())
+        .manage(MailPollState::default())
+        .manage(UpdaterPreferences::default())
+        .invoke_handler(tauri::generate_handler![
+            window_manager::open_compose,
+            window_manager::focus_or_open,
+            window_manager::close_window,
+            tray::set_poll_interval,
+            tray::enable_background_checks,
+            updater::check_for_updates,
+            updater::set_auto_update,
+            updater::set_update_channel,
+            diagnostics::set_compatibility_rendering,
+        ])
         .setup(|app| {
-            // Create main window pointing to nubo.email
-            let url = WebviewUrl::External("https://nubo.email/login".parse().unwrap());
+            // Apply known-good per-platform env/webview flags before building
+            // any window, so the common blank-window driver issues never get
+            // a chance to occur.
+            diagnostics::apply_platform_workarounds(&app.handle());
+
+            // Start on the embedded offline page — it never needs the
+            // network — and stay hidden until the connectivity probe (run
+            // off the main thread so it can never stall startup) decides
+            // whether to navigate to the real login page before showing.
+            let url = offline::offline_webview_url();
+
+            let load_watcher = diagnostics::LoadWatcher::new();
 
             #[cfg(desktop)]
             {
@@ -16,7 +64,8 @@ pub fn run() {
                     .resizable(true)
                     .fullscreen(false)
                     .decorations(true)
-                    .visible(true)
+                    .visible(false)
+                    .on_page_load(load_watcher.on_page_load())
                     .build()?;
 
                 // macOS: Use overlay title bar for cleaner look
@@ -27,15 +76,56 @@ pub fn run() {
                 }
 
                 #[cfg(not(target_os = "macos"))]
-                let _ = window;
+                let _ = &window;
+
+                // Hide to tray instead of quitting while background mail
+                // checking is enabled.
+                let app_handle = app.handle().clone();
+                window.on_window_event(move |event| {
+                    if let WindowEvent::CloseRequested { api, .. } = event {
+                        if app_handle.state::<MailPollState>().is_enabled() {
+                            api.prevent_close();
+                            if let Some(window) = app_handle.get_webview_window("main") {
+                                let _ = window.hide();
+                            }
+                        }
+                    }
+                });
             }
 
             #[cfg(mobile)]
             {
                 let _window = WebviewWindowBuilder::new(app, "main", url)
+                    .visible(false)
+                    .on_page_load(load_watcher.on_page_load())
                     .build()?;
             }
 
+            load_watcher.spawn_timeout_check(&app.handle());
+
+            offline::resolve_and_show(&app.handle());
+
+            tray::build_tray(&app.handle())?;
+            tray::spawn_background_polling(&app.handle());
+
+            // Register Nubo as the mailto:/nubo:// handler and route any
+            // link opened while we're already running to a compose window.
+            app.deep_link().register_all()?;
+            let deep_link_handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    deep_link::open_compose_from_uri(&deep_link_handle, url.as_str());
+                }
+            });
+
+            // Cold start: the OS may have launched us directly with a
+            // mailto:/nubo:// URI as an argument.
+            if let Some(uri) = deep_link::find_uri_in_args(std::env::args()) {
+                deep_link::open_compose_from_uri(&app.handle(), &uri);
+            }
+
+            updater::spawn_startup_check(&app.handle());
+
             Ok(())
         })
         .run(tauri::generate_context!())
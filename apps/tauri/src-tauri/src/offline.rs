@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use include_dir::{include_dir, Dir};
+use tauri::{http, AppHandle, Builder, Manager, Url, WebviewUrl, Wry};
+
+/// Minimal static bundle (cached inbox shell + a "reconnect" affordance)
+/// served when `nubo.email` can't be reached.
+static OFFLINE_ASSETS: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/offline");
+
+const REMOTE_ORIGIN: &str = "https://nubo.email";
+const OFFLINE_SCHEME: &str = "nubo-offline";
+const RECONNECT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Registers the `nubo-offline://` scheme that serves [`OFFLINE_ASSETS`] with
+/// MIME types inferred from the requested path.
+pub fn register_protocol(builder: Builder<Wry>) -> Builder<Wry> {
+    builder.register_uri_scheme_protocol(OFFLINE_SCHEME, |_app, request| {
+        let path = request.uri().path().trim_start_matches('/');
+        let path = if path.is_empty() { "index.html" } else { path };
+
+        match OFFLINE_ASSETS.get_file(path) {
+            Some(file) => {
+                let mime = mime_guess::from_path(path).first_or_octet_stream();
+                http::Response::builder()
+                    .header(http::header::CONTENT_TYPE, mime.as_ref())
+                    .body(file.contents().to_vec())
+                    .unwrap()
+            }
+            None => http::Response::builder()
+                .status(http::StatusCode::NOT_FOUND)
+                .body(Vec::new())
+                .unwrap(),
+        }
+    })
+}
+
+fn offline_url() -> Url {
+    format!("{OFFLINE_SCHEME}://localhost/index.html")
+        .parse()
+        .expect("offline URL is valid")
+}
+
+fn login_url() -> Url {
+    format!("{REMOTE_ORIGIN}/login")
+        .parse()
+        .expect("login URL is valid")
+}
+
+/// The `WebviewUrl` the main window should start on: registered as a real
+/// custom protocol (not `External`) so it resolves correctly on WebView2 as
+/// well as WebKitGTK/WKWebView.
+pub fn offline_webview_url() -> WebviewUrl {
+    WebviewUrl::CustomProtocol(offline_url())
+}
+
+/// Performs a lightweight, blocking connectivity probe against `nubo.email`.
+/// Callers must run this off whichever thread they can't afford to stall —
+/// `spawn_blocking` from an async context, never inline in `setup`.
+fn probe_online() -> bool {
+    ureq::get(REMOTE_ORIGIN)
+        .timeout(PROBE_TIMEOUT)
+        .call()
+        .is_ok()
+}
+
+/// Probes connectivity on a blocking thread, then points the main window at
+/// the real login page or the embedded offline page and makes it visible.
+/// Building the window itself starts immediately in `setup`; this just
+/// decides what it should show once the (up to 3s) probe resolves, so the
+/// probe never blocks window creation or the rest of startup.
+pub fn resolve_and_show(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let online = tauri::async_runtime::spawn_blocking(probe_online)
+            .await
+            .unwrap_or(false);
+
+        let Some(window) = app.get_webview_window("main") else {
+            return;
+        };
+
+        if online {
+            let _ = window.navigate(login_url());
+        }
+        let _ = window.show();
+
+        watch_connectivity(&app, !online);
+    });
+}
+
+/// Spawns a background task that watches connectivity and transparently
+/// swaps the main window between the offline bundle and the real site as
+/// reachability changes.
+fn watch_connectivity(app: &AppHandle, mut currently_offline: bool) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(RECONNECT_POLL_INTERVAL).await;
+
+            let online = tauri::async_runtime::spawn_blocking(probe_online)
+                .await
+                .unwrap_or(false);
+            let Some(window) = app.get_webview_window("main") else {
+                continue;
+            };
+
+            if online && currently_offline {
+                if window.navigate(login_url()).is_ok() {
+                    currently_offline = false;
+                }
+            } else if !online && !currently_offline {
+                if window.navigate(offline_url()).is_ok() {
+                    currently_offline = true;
+                }
+            }
+        }
+    });
+}
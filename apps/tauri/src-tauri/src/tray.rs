@@ -0,0 +1,190 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Deserialize;
+use tauri::menu::{Menu, MenuItem};
+use tauri::tray::{TrayIcon, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(60);
+const UNREAD_COUNT_URL: &str = "https://nubo.email/api/unread-count";
+
+/// Shared state backing the background mail-polling loop: how often to
+/// poll, whether polling is enabled at all, and the last unread count we
+/// saw (used to detect "new mail" transitions for notifications).
+pub struct MailPollState {
+    interval_secs: Mutex<u64>,
+    enabled: AtomicBool,
+    last_unread: AtomicU64,
+    /// Whether we've completed at least one poll. The very first poll after
+    /// launch only seeds `last_unread` — pre-existing unread mail shouldn't
+    /// fire a "new mail" notification on every single app start.
+    polled_once: AtomicBool,
+}
+
+impl Default for MailPollState {
+    fn default() -> Self {
+        Self {
+            interval_secs: Mutex::new(DEFAULT_POLL_INTERVAL.as_secs()),
+            enabled: AtomicBool::new(true),
+            last_unread: AtomicU64::new(0),
+            polled_once: AtomicBool::new(false),
+        }
+    }
+}
+
+impl MailPollState {
+    fn interval(&self) -> Duration {
+        Duration::from_secs(*self.interval_secs.lock().unwrap())
+    }
+
+    /// Whether the window's close handler should hide to tray instead of
+    /// quitting the app.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Deserialize)]
+struct UnreadCountResponse {
+    unread: u64,
+}
+
+fn fetch_unread_count() -> Option<u64> {
+    ureq::get(UNREAD_COUNT_URL)
+        .timeout(Duration::from_secs(5))
+        .call()
+        .ok()?
+        .into_json::<UnreadCountResponse>()
+        .ok()
+        .map(|response| response.unread)
+}
+
+fn focus_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Updates the tray icon title and, on macOS, the dock badge to reflect the
+/// current unread count.
+fn update_unread_indicators(app: &AppHandle, count: u64) {
+    let title = if count == 0 {
+        None
+    } else {
+        Some(count.to_string())
+    };
+    let _ = app.state::<TrayIcon>().set_title(title.as_deref());
+
+    #[cfg(target_os = "macos")]
+    {
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.set_badge_count(if count == 0 { None } else { Some(count as i64) });
+        }
+    }
+}
+
+/// Updates the tray/badge for `count` and fires a notification if it grew
+/// since the last poll.
+fn apply_unread_count(app: &AppHandle, count: u64) {
+    update_unread_indicators(app, count);
+
+    let state = app.state::<MailPollState>();
+    let previous = state.last_unread.swap(count, Ordering::Relaxed);
+    let first_poll = !state.polled_once.swap(true, Ordering::Relaxed);
+    if !first_poll && count > previous {
+        let _ = app
+            .notification()
+            .builder()
+            .title("New mail")
+            .body(format!("You have {count} unread message(s)"))
+            .show();
+    }
+}
+
+/// Fetches the unread count once, updates the tray/badge, and fires a
+/// notification if the count grew since the last poll. Called directly from
+/// the tray menu's "Check Mail" handler, which runs on a plain callback
+/// thread rather than a tokio task, so blocking here doesn't risk starving
+/// the shared runtime.
+fn poll_once(app: &AppHandle) {
+    let Some(count) = fetch_unread_count() else {
+        return;
+    };
+
+    apply_unread_count(app, count);
+}
+
+/// Builds the tray icon and its menu (Open Nubo, Compose, Check Mail, Quit).
+pub fn build_tray(app: &AppHandle) -> tauri::Result<()> {
+    let open = MenuItem::with_id(app, "open", "Open Nubo", true, None::<&str>)?;
+    let compose = MenuItem::with_id(app, "compose", "Compose", true, None::<&str>)?;
+    let check_mail = MenuItem::with_id(app, "check_mail", "Check Mail", true, None::<&str>)?;
+    let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    let menu = Menu::with_items(app, &[&open, &compose, &check_mail, &quit])?;
+
+    let tray = TrayIconBuilder::new()
+        .menu(&menu)
+        .tooltip("Nubo")
+        .on_menu_event(|app, event| match event.id().as_ref() {
+            "open" => focus_main_window(app),
+            "compose" => {
+                let _ = crate::window_manager::open_compose(
+                    app.clone(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                );
+            }
+            "check_mail" => poll_once(app),
+            "quit" => app.exit(0),
+            _ => {}
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click { .. } = event {
+                focus_main_window(tray.app_handle());
+            }
+        })
+        .build(app)?;
+
+    app.manage(tray);
+    Ok(())
+}
+
+/// Spawns the background loop that periodically polls the unread count.
+/// Sleeps for [`MailPollState::interval`] between checks, re-reading it each
+/// iteration so `set_poll_interval` takes effect without a restart. The
+/// blocking fetch itself runs via `spawn_blocking` so it can't stall the
+/// shared tokio runtime while other polling loops or the updater check are
+/// competing for worker threads.
+pub fn spawn_background_polling(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let interval = app.state::<MailPollState>().interval();
+            tokio::time::sleep(interval).await;
+
+            if app.state::<MailPollState>().is_enabled() {
+                if let Ok(Some(count)) = tauri::async_runtime::spawn_blocking(fetch_unread_count).await {
+                    apply_unread_count(&app, count);
+                }
+            }
+        }
+    });
+}
+
+/// Sets how often the background loop polls for unread mail.
+#[tauri::command]
+pub fn set_poll_interval(state: tauri::State<MailPollState>, seconds: u64) {
+    *state.interval_secs.lock().unwrap() = seconds.max(1);
+}
+
+/// Enables or disables background mail checking. When disabled, closing the
+/// main window quits the app instead of hiding it to the tray.
+#[tauri::command]
+pub fn enable_background_checks(state: tauri::State<MailPollState>, enabled: bool) {
+    state.enabled.store(enabled, Ordering::Relaxed);
+}
@@ -0,0 +1,139 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Url};
+use tauri_plugin_updater::UpdaterExt;
+
+/// Release channel the updater checks against. Each channel has its own
+/// manifest endpoint (configured server-side), so switching channels
+/// actually changes what the next check fetches.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    Stable,
+    Beta,
+}
+
+impl Channel {
+    fn endpoint(self) -> Url {
+        let template = match self {
+            Channel::Stable => "https://nubo.email/releases/stable/{{target}}/{{arch}}/{{current_version}}",
+            Channel::Beta => "https://nubo.email/releases/beta/{{target}}/{{arch}}/{{current_version}}",
+        };
+        template.parse().expect("updater endpoint template is a valid URL")
+    }
+}
+
+/// User preferences for the self-updater: install automatically or wait for
+/// the user to confirm, and which release channel to track.
+pub struct UpdaterPreferences {
+    auto_install: AtomicBool,
+    channel: Mutex<Channel>,
+}
+
+impl Default for UpdaterPreferences {
+    fn default() -> Self {
+        Self {
+            auto_install: AtomicBool::new(true),
+            channel: Mutex::new(Channel::Stable),
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
+struct UpdateProgress {
+    downloaded: usize,
+    total: Option<u64>,
+}
+
+/// Checks the release manifest for a newer, signature-verified build
+/// (verification against the embedded public key is handled by the updater
+/// plugin itself) and, if the user has auto-install on, downloads and
+/// applies it, emitting `update://progress` events the frontend can render
+/// as a progress bar before relaunching.
+async fn check_and_apply(app: &AppHandle) -> Result<(), String> {
+    let channel = *app.state::<UpdaterPreferences>().channel.lock().unwrap();
+    let updater = app
+        .updater_builder()
+        .endpoints(vec![channel.endpoint()])
+        .map_err(|err| err.to_string())?
+        .build()
+        .map_err(|err| err.to_string())?;
+
+    let update = match updater.check().await {
+        Ok(Some(update)) => update,
+        Ok(None) => {
+            let _ = app.emit("update://not-available", ());
+            return Ok(());
+        }
+        Err(err) => {
+            let _ = app.emit("update://error", err.to_string());
+            return Err(err.to_string());
+        }
+    };
+
+    let auto_install = app
+        .state::<UpdaterPreferences>()
+        .auto_install
+        .load(Ordering::Relaxed);
+    if !auto_install {
+        let _ = app.emit("update://available", update.version.clone());
+        return Ok(());
+    }
+
+    let app_handle = app.clone();
+    let downloaded = AtomicUsize::new(0);
+    update
+        .download_and_install(
+            move |chunk_len, total| {
+                let so_far = downloaded.fetch_add(chunk_len, Ordering::Relaxed) + chunk_len;
+                let _ = app_handle.emit(
+                    "update://progress",
+                    UpdateProgress {
+                        downloaded: so_far,
+                        total,
+                    },
+                );
+            },
+            || {},
+        )
+        .await
+        .map_err(|err| err.to_string())?;
+
+    app.request_restart();
+    Ok(())
+}
+
+/// Runs an update check, surfacing failures through `update://error` rather
+/// than failing silently.
+#[tauri::command]
+pub async fn check_for_updates(app: AppHandle) -> Result<(), String> {
+    check_and_apply(&app).await
+}
+
+/// Switches between installing updates automatically and only notifying the
+/// frontend that one is available.
+#[tauri::command]
+pub fn set_auto_update(state: tauri::State<UpdaterPreferences>, enabled: bool) {
+    state.auto_install.store(enabled, Ordering::Relaxed);
+}
+
+/// Switches the release channel the updater checks (`stable` or `beta`).
+#[tauri::command]
+pub fn set_update_channel(state: tauri::State<UpdaterPreferences>, channel: String) {
+    let channel = if channel.eq_ignore_ascii_case("beta") {
+        Channel::Beta
+    } else {
+        Channel::Stable
+    };
+    *state.channel.lock().unwrap() = channel;
+}
+
+/// Kicks off the startup update check in the background so it never blocks
+/// window creation.
+pub fn spawn_startup_check(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let _ = check_and_apply(&app).await;
+    });
+}
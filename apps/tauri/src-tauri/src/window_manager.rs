@@ -0,0 +1,92 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use tauri::{AppHandle, Manager, Url, WebviewUrl, WebviewWindowBuilder};
+
+const COMPOSE_URL: &str = "https://nubo.email/compose";
+
+/// Builds `https://nubo.email/compose` with `to`/`subject`/`body` query
+/// params, percent-encoding each value so it round-trips through the webview.
+fn compose_url(recipient: &str, subject: &str, body: &str) -> Url {
+    let mut url = Url::parse(COMPOSE_URL).expect("COMPOSE_URL is a valid URL");
+    url.query_pairs_mut()
+        .append_pair("to", recipient)
+        .append_pair("subject", subject)
+        .append_pair("body", body);
+    url
+}
+
+/// Hands out a fresh label for each new compose window, e.g. `compose-0`,
+/// `compose-1`, ...
+fn next_compose_label() -> String {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    format!("compose-{}", NEXT.fetch_add(1, Ordering::Relaxed))
+}
+
+/// Focuses `label` if it already exists, otherwise builds it.
+///
+/// Window creation must happen on the main thread. Building a window from
+/// inside an async `#[tauri::command]` body (i.e. after a `.await`) can
+/// overflow the main thread's stack on Windows and leaves a blank window
+/// behind, so `WebviewWindowBuilder::build()` is never called directly from
+/// the command handler. Instead we hop onto the main thread via
+/// `run_on_main_thread` and block on a oneshot channel until that closure
+/// has actually finished building the window, so we never return to the
+/// frontend before the window exists (a command result resolving early
+/// could otherwise race a follow-up `focus_or_open`/`close_window` call).
+fn open_or_focus(app: &AppHandle, label: &str, url: Url) -> tauri::Result<()> {
+    if let Some(window) = app.get_webview_window(label) {
+        window.show()?;
+        window.set_focus()?;
+        return Ok(());
+    }
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let app_handle = app.clone();
+    let label_owned = label.to_string();
+    app.run_on_main_thread(move || {
+        let built = WebviewWindowBuilder::new(&app_handle, &label_owned, WebviewUrl::External(url))
+            .title("New Message")
+            .inner_size(760.0, 620.0)
+            .min_inner_size(480.0, 360.0)
+            .build();
+
+        if let Err(err) = &built {
+            log::error!("failed to build compose window \"{label_owned}\": {err}");
+        }
+
+        // The receiver can't have been dropped: we block on `rx.recv()`
+        // right below before returning.
+        let _ = tx.send(built.map(|_| ()));
+    })?;
+
+    rx.recv().map_err(|_| tauri::Error::FailedToReceiveMessage)?
+}
+
+/// Opens a new compose window pre-filled with `recipient`/`subject`/`body`.
+#[tauri::command]
+pub fn open_compose(
+    app: AppHandle,
+    recipient: String,
+    subject: String,
+    body: String,
+) -> tauri::Result<()> {
+    let label = next_compose_label();
+    open_or_focus(&app, &label, compose_url(&recipient, &subject, &body))
+}
+
+/// Focuses the window with `label` if it is already open, otherwise opens a
+/// blank compose window under that label.
+#[tauri::command]
+pub fn focus_or_open(app: AppHandle, label: String) -> tauri::Result<()> {
+    let url = Url::parse(COMPOSE_URL).expect("COMPOSE_URL is a valid URL");
+    open_or_focus(&app, &label, url)
+}
+
+/// Closes the window with `label`, if it exists.
+#[tauri::command]
+pub fn close_window(app: AppHandle, label: String) -> tauri::Result<()> {
+    if let Some(window) = app.get_webview_window(&label) {
+        window.close()?;
+    }
+    Ok(())
+}